@@ -0,0 +1,113 @@
+use crate::{deflate, packbits, predictor, Predictor};
+
+/// Which TIFF compression scheme tiles are stored with.
+///
+/// This maps directly onto the TIFF `Compression` tag (0x103) values written
+/// into each level's IFD.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Compression {
+    /// No compression (TIFF tag value 1).
+    None,
+    /// LZW (TIFF tag value 5), via the `weezl` crate.
+    Lzw,
+    /// Adobe-style zlib/Deflate (TIFF tag value 8).
+    Deflate,
+    /// PackBits run-length encoding (TIFF tag value 32773).
+    PackBits,
+}
+
+impl Compression {
+    pub(crate) fn tiff_tag_value(self) -> u64 {
+        match self {
+            Compression::None => 1,
+            Compression::Lzw => 5,
+            Compression::Deflate => 8,
+            Compression::PackBits => 32773,
+        }
+    }
+}
+
+fn adler32(data: &[u8]) -> u32 {
+    const MODULO: u32 = 65521;
+    let mut a = 1u32;
+    let mut b = 0u32;
+    for &byte in data {
+        a = (a + byte as u32) % MODULO;
+        b = (b + a) % MODULO;
+    }
+    (b << 16) | a
+}
+
+fn zlib_compress(data: &[u8]) -> Vec<u8> {
+    let mut out = Vec::with_capacity(data.len() / 2 + 6);
+    out.extend_from_slice(&[0x78, 0x9C]);
+    out.extend(deflate::compress(data));
+    out.extend_from_slice(&adler32(data).to_be_bytes());
+    out
+}
+
+fn zlib_decompress(data: &[u8]) -> Result<Vec<u8>, anyhow::Error> {
+    anyhow::ensure!(data.len() >= 6, "zlib stream too short");
+    anyhow::ensure!(
+        data[0] == 0x78 && data[1] == 0x9C,
+        "unsupported zlib header {:#04x} {:#04x}",
+        data[0],
+        data[1]
+    );
+    let body = &data[2..data.len() - 4];
+    let decoded = deflate::decompress(body)?;
+
+    let checksum = u32::from_be_bytes(data[data.len() - 4..].try_into().unwrap());
+    anyhow::ensure!(adler32(&decoded) == checksum, "zlib Adler-32 checksum mismatch");
+    Ok(decoded)
+}
+
+/// Compresses a tile's raw bytes with the given scheme, first applying
+/// `predictor` (see [`crate::predictor`]) using `samples_per_pixel`,
+/// `bytes_per_sample`, and the tile's actual sample `width`.
+pub fn compress_tile(
+    data: &[u8],
+    compression: Compression,
+    predictor: Predictor,
+    samples_per_pixel: usize,
+    width: u32,
+    bytes_per_sample: usize,
+) -> Vec<u8> {
+    let data = predictor::apply(data, predictor, samples_per_pixel, width, bytes_per_sample);
+    match compression {
+        Compression::None => data,
+        Compression::Lzw => weezl::encode::Encoder::with_tiff_size_switch(weezl::BitOrder::Msb, 8)
+            .encode(&data)
+            .unwrap(),
+        Compression::Deflate => zlib_compress(&data),
+        Compression::PackBits => packbits::encode(&data),
+    }
+}
+
+/// Decompresses a tile's bytes, which must have been produced by
+/// [`compress_tile`] with the same `compression`, `predictor`,
+/// `samples_per_pixel`, `width`, and `bytes_per_sample`.
+pub fn decompress_tile(
+    data: &[u8],
+    compression: Compression,
+    predictor: Predictor,
+    samples_per_pixel: usize,
+    width: u32,
+    bytes_per_sample: usize,
+) -> Result<Vec<u8>, anyhow::Error> {
+    let decompressed = match compression {
+        Compression::None => data.to_vec(),
+        Compression::Lzw => {
+            weezl::decode::Decoder::with_tiff_size_switch(weezl::BitOrder::Msb, 8).decode(data)?
+        }
+        Compression::Deflate => zlib_decompress(data)?,
+        Compression::PackBits => packbits::decode(data)?,
+    };
+    Ok(predictor::unapply(
+        &decompressed,
+        predictor,
+        samples_per_pixel,
+        width,
+        bytes_per_sample,
+    ))
+}
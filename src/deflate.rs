@@ -0,0 +1,332 @@
+//! A small, dependency-free DEFLATE (RFC 1951) codec, wrapped in zlib framing
+//! by [`crate::compression`]. The encoder only ever emits a single fixed
+//! Huffman block; the decoder understands stored and fixed Huffman blocks,
+//! but not dynamic Huffman ones.
+
+use std::collections::HashMap;
+
+use anyhow::{bail, ensure};
+
+const LENGTH_BASE: [u16; 29] = [
+    3, 4, 5, 6, 7, 8, 9, 10, 11, 13, 15, 17, 19, 23, 27, 31, 35, 43, 51, 59, 67, 83, 99, 115, 131,
+    163, 195, 227, 258,
+];
+const LENGTH_EXTRA_BITS: [u8; 29] = [
+    0, 0, 0, 0, 0, 0, 0, 0, 1, 1, 1, 1, 2, 2, 2, 2, 3, 3, 3, 3, 4, 4, 4, 4, 5, 5, 5, 5, 0,
+];
+const DISTANCE_BASE: [u16; 30] = [
+    1, 2, 3, 4, 5, 7, 9, 13, 17, 25, 33, 49, 65, 97, 129, 193, 257, 385, 513, 769, 1025, 1537,
+    2049, 3073, 4097, 6145, 8193, 12289, 16385, 24577,
+];
+const DISTANCE_EXTRA_BITS: [u8; 30] = [
+    0, 0, 0, 0, 1, 1, 2, 2, 3, 3, 4, 4, 5, 5, 6, 6, 7, 7, 8, 8, 9, 9, 10, 10, 11, 11, 12, 12, 13,
+    13,
+];
+
+const MIN_MATCH: usize = 3;
+const MAX_MATCH: usize = 258;
+const MAX_DISTANCE: usize = 32768;
+
+struct BitWriter {
+    bytes: Vec<u8>,
+    cur: u8,
+    nbits: u8,
+}
+impl BitWriter {
+    fn new() -> Self {
+        Self {
+            bytes: Vec::new(),
+            cur: 0,
+            nbits: 0,
+        }
+    }
+    fn write_bit(&mut self, bit: u8) {
+        self.cur |= bit << self.nbits;
+        self.nbits += 1;
+        if self.nbits == 8 {
+            self.bytes.push(self.cur);
+            self.cur = 0;
+            self.nbits = 0;
+        }
+    }
+    /// Writes `n` bits of `value`, least-significant bit first.
+    fn write_bits_lsb(&mut self, value: u32, n: u8) {
+        for i in 0..n {
+            self.write_bit(((value >> i) & 1) as u8);
+        }
+    }
+    /// Writes a Huffman code, most-significant bit first (per RFC 1951 3.1.1).
+    fn write_huffman(&mut self, code: u16, length: u8) {
+        for i in (0..length).rev() {
+            self.write_bit(((code >> i) & 1) as u8);
+        }
+    }
+    fn finish(mut self) -> Vec<u8> {
+        if self.nbits > 0 {
+            self.bytes.push(self.cur);
+        }
+        self.bytes
+    }
+}
+
+/// Fixed Huffman code for a literal/length symbol (0..=287), per RFC 1951 3.2.6.
+fn fixed_litlen_code(symbol: u16) -> (u16, u8) {
+    match symbol {
+        0..=143 => (0b0011_0000 + symbol, 8),
+        144..=255 => (0b1_1001_0000 + (symbol - 144), 9),
+        256..=279 => (symbol - 256, 7),
+        280..=287 => (0b1100_0000 + (symbol - 280), 8),
+        _ => unreachable!(),
+    }
+}
+
+fn length_code(length: usize) -> (u16, u8, u32) {
+    let idx = LENGTH_BASE
+        .iter()
+        .rposition(|&base| base as usize <= length)
+        .unwrap();
+    let extra = (length - LENGTH_BASE[idx] as usize) as u32;
+    (257 + idx as u16, LENGTH_EXTRA_BITS[idx], extra)
+}
+
+fn distance_code(distance: usize) -> (u16, u8, u32) {
+    let idx = DISTANCE_BASE
+        .iter()
+        .rposition(|&base| base as usize <= distance)
+        .unwrap();
+    let extra = (distance - DISTANCE_BASE[idx] as usize) as u32;
+    (idx as u16, DISTANCE_EXTRA_BITS[idx], extra)
+}
+
+/// Finds the longest match for `data[pos..]` among the 32 most recent
+/// previously-seen positions sharing its 3-byte prefix, using a simple
+/// chained hash table of 3-byte prefixes.
+fn find_match(
+    data: &[u8],
+    pos: usize,
+    chains: &HashMap<usize, Vec<u32>>,
+    bucket: usize,
+) -> Option<(usize, usize)> {
+    let mut best_len = 0;
+    let mut best_dist = 0;
+    let max_len = MAX_MATCH.min(data.len() - pos);
+    if max_len < MIN_MATCH {
+        return None;
+    }
+    let chain = chains.get(&bucket)?;
+    for &candidate in chain.iter().rev().take(32) {
+        let candidate = candidate as usize;
+        if pos - candidate > MAX_DISTANCE {
+            continue;
+        }
+        let mut len = 0;
+        while len < max_len && data[candidate + len] == data[pos + len] {
+            len += 1;
+        }
+        if len > best_len {
+            best_len = len;
+            best_dist = pos - candidate;
+        }
+    }
+    if best_len >= MIN_MATCH {
+        Some((best_len, best_dist))
+    } else {
+        None
+    }
+}
+
+fn hash3(data: &[u8], pos: usize) -> usize {
+    (data[pos] as usize) << 16 | (data[pos + 1] as usize) << 8 | data[pos + 2] as usize
+}
+
+/// Compresses `data` into a raw (headerless) DEFLATE stream using LZ77 matching
+/// and the fixed Huffman tables.
+pub fn compress(data: &[u8]) -> Vec<u8> {
+    let mut writer = BitWriter::new();
+    if data.is_empty() {
+        writer.write_bit(1); // BFINAL
+        writer.write_bits_lsb(0b01, 2); // BTYPE = fixed Huffman
+        let (code, len) = fixed_litlen_code(256);
+        writer.write_huffman(code, len);
+        return writer.finish();
+    }
+
+    writer.write_bit(1); // BFINAL: this is the only block we emit
+    writer.write_bits_lsb(0b01, 2); // BTYPE = fixed Huffman
+
+    let mut chains: HashMap<usize, Vec<u32>> = HashMap::new();
+    let mut pos = 0;
+    while pos < data.len() {
+        if pos + MIN_MATCH <= data.len() {
+            let bucket = hash3(data, pos);
+            let found = find_match(data, pos, &chains, bucket);
+            chains.entry(bucket).or_default().push(pos as u32);
+            if let Some((len, dist)) = found {
+                let (lcode, lextra_bits, lextra) = length_code(len);
+                let (lhcode, lhlen) = fixed_litlen_code(lcode);
+                writer.write_huffman(lhcode, lhlen);
+                writer.write_bits_lsb(lextra, lextra_bits);
+
+                let (dcode, dextra_bits, dextra) = distance_code(dist);
+                writer.write_huffman(dcode, 5);
+                writer.write_bits_lsb(dextra, dextra_bits);
+
+                for i in 1..len {
+                    if pos + i + MIN_MATCH <= data.len() {
+                        let bucket = hash3(data, pos + i);
+                        chains.entry(bucket).or_default().push((pos + i) as u32);
+                    }
+                }
+                pos += len;
+                continue;
+            }
+        }
+        let (code, len) = fixed_litlen_code(data[pos] as u16);
+        writer.write_huffman(code, len);
+        pos += 1;
+    }
+
+    let (code, len) = fixed_litlen_code(256); // end-of-block
+    writer.write_huffman(code, len);
+    writer.finish()
+}
+
+struct BitReader<'a> {
+    bytes: &'a [u8],
+    byte_pos: usize,
+    bit_pos: u8,
+}
+impl<'a> BitReader<'a> {
+    fn new(bytes: &'a [u8]) -> Self {
+        Self {
+            bytes,
+            byte_pos: 0,
+            bit_pos: 0,
+        }
+    }
+    fn read_bit(&mut self) -> Result<u8, anyhow::Error> {
+        ensure!(self.byte_pos < self.bytes.len(), "unexpected end of deflate stream");
+        let bit = (self.bytes[self.byte_pos] >> self.bit_pos) & 1;
+        self.bit_pos += 1;
+        if self.bit_pos == 8 {
+            self.bit_pos = 0;
+            self.byte_pos += 1;
+        }
+        Ok(bit)
+    }
+    fn read_bits_lsb(&mut self, n: u8) -> Result<u32, anyhow::Error> {
+        let mut value = 0u32;
+        for i in 0..n {
+            value |= (self.read_bit()? as u32) << i;
+        }
+        Ok(value)
+    }
+    fn align_to_byte(&mut self) {
+        if self.bit_pos != 0 {
+            self.bit_pos = 0;
+            self.byte_pos += 1;
+        }
+    }
+}
+
+fn decode_fixed_litlen(reader: &mut BitReader) -> Result<u16, anyhow::Error> {
+    // Fixed Huffman codes are prefix-free once the first 7 bits are known:
+    // 7-bit codes 0..=23 are symbols 256..=279 (end-of-block and the first
+    // length symbols); everything else needs
+    // one or two more bits to disambiguate, per RFC 1951 3.2.6.
+    let mut code = 0u16;
+    for _ in 0..7 {
+        code = (code << 1) | reader.read_bit()? as u16;
+    }
+    if code <= 0b001_0111 {
+        return Ok(256 + code);
+    }
+    code = (code << 1) | reader.read_bit()? as u16;
+    if (0b0011_0000..=0b1011_1111).contains(&code) {
+        return Ok(code - 0b0011_0000);
+    }
+    if (0b1100_0000..=0b1100_0111).contains(&code) {
+        return Ok(280 + (code - 0b1100_0000));
+    }
+    code = (code << 1) | reader.read_bit()? as u16;
+    if (0b1_1001_0000..=0b1_1111_1111).contains(&code) {
+        return Ok(144 + (code - 0b1_1001_0000));
+    }
+    bail!("invalid fixed Huffman code");
+}
+
+/// Decompresses a raw DEFLATE stream produced by [`compress`]. Only stored
+/// and fixed-Huffman blocks are understood (the encoder emits fixed-Huffman
+/// blocks only); dynamic-Huffman blocks are rejected.
+pub fn decompress(data: &[u8]) -> Result<Vec<u8>, anyhow::Error> {
+    let mut reader = BitReader::new(data);
+    let mut out = Vec::new();
+    loop {
+        let bfinal = reader.read_bit()?;
+        let btype = reader.read_bits_lsb(2)?;
+        match btype {
+            0 => {
+                reader.align_to_byte();
+                ensure!(reader.byte_pos + 4 <= reader.bytes.len(), "truncated stored block");
+                let len = u16::from_le_bytes(
+                    reader.bytes[reader.byte_pos..reader.byte_pos + 2]
+                        .try_into()
+                        .unwrap(),
+                ) as usize;
+                reader.byte_pos += 4; // len + one's complement of len
+                ensure!(reader.byte_pos + len <= reader.bytes.len(), "truncated stored block data");
+                out.extend_from_slice(&reader.bytes[reader.byte_pos..reader.byte_pos + len]);
+                reader.byte_pos += len;
+            }
+            1 => loop {
+                let symbol = decode_fixed_litlen(&mut reader)?;
+                if symbol < 256 {
+                    out.push(symbol as u8);
+                } else if symbol == 256 {
+                    break;
+                } else {
+                    let idx = (symbol - 257) as usize;
+                    ensure!(idx < LENGTH_BASE.len(), "invalid length code");
+                    let length = LENGTH_BASE[idx] as usize
+                        + reader.read_bits_lsb(LENGTH_EXTRA_BITS[idx])? as usize;
+
+                    let mut dcode = 0u16;
+                    for _ in 0..5 {
+                        dcode = (dcode << 1) | reader.read_bit()? as u16;
+                    }
+                    let didx = dcode as usize;
+                    ensure!(didx < DISTANCE_BASE.len(), "invalid distance code");
+                    let distance = DISTANCE_BASE[didx] as usize
+                        + reader.read_bits_lsb(DISTANCE_EXTRA_BITS[didx])? as usize;
+
+                    ensure!(distance <= out.len(), "match distance exceeds output so far");
+                    let start = out.len() - distance;
+                    for i in 0..length {
+                        let byte = out[start + i];
+                        out.push(byte);
+                    }
+                }
+            },
+            _ => bail!("unsupported DEFLATE block type {btype}"),
+        }
+        if bfinal == 1 {
+            break;
+        }
+    }
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn roundtrip() {
+        let mut data = vec![0u8; 4096];
+        for (i, b) in data.iter_mut().enumerate() {
+            *b = (i % 7) as u8;
+        }
+        assert_eq!(decompress(&compress(&data)).unwrap(), data);
+        assert_eq!(decompress(&compress(&[])).unwrap(), Vec::<u8>::new());
+    }
+}
@@ -0,0 +1,113 @@
+//! Minimal GeoTIFF georeferencing: writing enough of tags 0x830E, 0x8482, and
+//! 0x87AF-0x87B1 into level 0's IFD that GDAL/QGIS recognize the output as a
+//! proper Cloud Optimized GeoTIFF with a CRS and geotransform.
+
+/// A coordinate reference system (by EPSG code) plus the affine transform
+/// from raster space to that CRS, expressed TIFF-style as a pixel scale and a
+/// single tiepoint (the georeferenced position of pixel (0, 0)).
+#[derive(Clone, Debug, PartialEq)]
+pub struct GeoReference {
+    pub epsg: u16,
+    pub pixel_scale: [f64; 3],
+    pub tiepoint: [f64; 6],
+}
+
+const GT_MODEL_TYPE_GEO_KEY: u16 = 1024;
+const GT_RASTER_TYPE_GEO_KEY: u16 = 1025;
+const GT_CITATION_GEO_KEY: u16 = 1026;
+const GEOGRAPHIC_CS_TYPE_GEO_KEY: u16 = 2048;
+const PROJECTED_CS_TYPE_GEO_KEY: u16 = 3072;
+const GEO_ASCII_PARAMS_TAG_ID: u16 = 0x87B1;
+
+const MODEL_TYPE_PROJECTED: u16 = 1;
+const MODEL_TYPE_GEOGRAPHIC: u16 = 2;
+
+/// Whether `epsg` names a geographic (lat/lon) CRS rather than a projected
+/// (planar, e.g. UTM) one. EPSG reserves 4000-4999 for geographic 2D CRSes
+/// (e.g. 4326 = WGS84); everything else handled by this crate is projected.
+fn is_geographic(epsg: u16) -> bool {
+    (4000..5000).contains(&epsg)
+}
+
+/// The georeferencing arrays to append to the file past the fixed per-level
+/// IFD area, plus the absolute file offset of each array. Level 0's
+/// `ModelPixelScaleTag`/`ModelTiepointTag`/`GeoKeyDirectoryTag`/etc entries
+/// point at these offsets.
+pub struct GeoBlob {
+    pub bytes: Vec<u8>,
+    pub pixel_scale_offset: u64,
+    pub tiepoint_offset: u64,
+    pub geo_key_directory_offset: u64,
+    pub geo_key_directory_len: u64,
+    pub geo_double_params_offset: u64,
+    pub geo_ascii_params_offset: u64,
+    pub geo_ascii_params_len: u64,
+}
+
+/// Builds the georeferencing blob for `geo`, assuming it will be written
+/// starting at absolute file offset `base_offset`.
+pub fn build_blob(geo: &GeoReference, base_offset: u64) -> GeoBlob {
+    let mut bytes = Vec::new();
+
+    let pixel_scale_offset = base_offset + bytes.len() as u64;
+    for v in geo.pixel_scale {
+        bytes.extend_from_slice(&v.to_le_bytes());
+    }
+
+    let tiepoint_offset = base_offset + bytes.len() as u64;
+    for v in geo.tiepoint {
+        bytes.extend_from_slice(&v.to_le_bytes());
+    }
+
+    // NUL-terminated per the GeoTIFF spec's rule for GeoAsciiParamsTag entries.
+    let citation = format!("EPSG:{}\0", geo.epsg);
+
+    let (model_type, cs_type_key) = if is_geographic(geo.epsg) {
+        (MODEL_TYPE_GEOGRAPHIC, GEOGRAPHIC_CS_TYPE_GEO_KEY)
+    } else {
+        (MODEL_TYPE_PROJECTED, PROJECTED_CS_TYPE_GEO_KEY)
+    };
+
+    // [KeyDirectoryVersion, KeyRevision, MinorRevision, NumberOfKeys] header,
+    // then one [KeyID, TagLocation, Count, Value_or_Offset] entry per key.
+    let keys: [[u16; 4]; 4] = [
+        [GT_MODEL_TYPE_GEO_KEY, 0, 1, model_type],
+        [GT_RASTER_TYPE_GEO_KEY, 0, 1, 1], // RasterPixelIsArea
+        [cs_type_key, 0, 1, geo.epsg],
+        [
+            GT_CITATION_GEO_KEY,
+            GEO_ASCII_PARAMS_TAG_ID,
+            citation.len() as u16,
+            0,
+        ],
+    ];
+    let geo_key_directory_offset = base_offset + bytes.len() as u64;
+    for v in [1u16, 1, 0, keys.len() as u16] {
+        bytes.extend_from_slice(&v.to_le_bytes());
+    }
+    for key in keys {
+        for v in key {
+            bytes.extend_from_slice(&v.to_le_bytes());
+        }
+    }
+    let geo_key_directory_len = (4 + keys.len() * 4) as u64;
+
+    // Reserved for future double-valued GeoKeys; none of the keys above use it.
+    let geo_double_params_offset = base_offset + bytes.len() as u64;
+    bytes.extend_from_slice(&0f64.to_le_bytes());
+
+    let geo_ascii_params_offset = base_offset + bytes.len() as u64;
+    let geo_ascii_params_len = citation.len() as u64;
+    bytes.extend_from_slice(citation.as_bytes());
+
+    GeoBlob {
+        bytes,
+        pixel_scale_offset,
+        tiepoint_offset,
+        geo_key_directory_offset,
+        geo_key_directory_len,
+        geo_double_params_offset,
+        geo_ascii_params_offset,
+        geo_ascii_params_len,
+    }
+}
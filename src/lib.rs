@@ -8,20 +8,98 @@ use std::{
 
 use thread_local::ThreadLocal;
 
+mod compression;
+mod deflate;
+mod geotiff;
+mod packbits;
+mod predictor;
+mod sample_format;
+
+pub use compression::{compress_tile, decompress_tile, Compression};
+pub use geotiff::GeoReference;
+pub use predictor::Predictor;
+pub use sample_format::SampleFormat;
+
 pub const TILE_SIZE: u32 = 1024;
 
-const NUM_TAGS: u32 = 12;
-const OFFSETS_TAG_INDEX: u64 = 9;
-const LENGTHS_TAG_INDEX: u64 = 10;
+const NUM_TAGS: u32 = 13;
+const OFFSETS_TAG_INDEX: u64 = 10;
+const LENGTHS_TAG_INDEX: u64 = 11;
 
-pub fn compress_tile(data: &[u8]) -> Vec<u8> {
-    weezl::encode::Encoder::with_tiff_size_switch(weezl::BitOrder::Msb, 8)
-        .encode(data)
-        .unwrap()
+/// Checks that `nodata` (if non-empty) parses as a number that fits in the
+/// sample type described by `bpp`/`sample_format`.
+fn validate_nodata(nodata: &str, bpp: &[u8], sample_format: SampleFormat) -> Result<(), anyhow::Error> {
+    if nodata.is_empty() {
+        return Ok(());
+    }
+    let bits = bpp.iter().copied().max().unwrap_or(8) as u32;
+    match sample_format {
+        SampleFormat::Int => {
+            let value: i64 = nodata
+                .parse()
+                .map_err(|_| anyhow::anyhow!("nodata value {nodata:?} is not a valid integer"))?;
+            let min = -(1i64 << (bits - 1));
+            let max = (1i64 << (bits - 1)) - 1;
+            anyhow::ensure!(
+                value >= min && value <= max,
+                "nodata value {value} does not fit in a signed {bits}-bit sample"
+            );
+        }
+        SampleFormat::Uint => {
+            let value: u64 = nodata
+                .parse()
+                .map_err(|_| anyhow::anyhow!("nodata value {nodata:?} is not a valid integer"))?;
+            let max = if bits >= 64 { u64::MAX } else { (1u64 << bits) - 1 };
+            anyhow::ensure!(
+                value <= max,
+                "nodata value {value} does not fit in an unsigned {bits}-bit sample"
+            );
+        }
+        SampleFormat::Float => {
+            anyhow::ensure!(
+                bits == 32 || bits == 64,
+                "nodata value {nodata:?} requires a 32- or 64-bit float sample"
+            );
+            nodata
+                .parse::<f64>()
+                .map_err(|_| anyhow::anyhow!("nodata value {nodata:?} is not a valid float"))?;
+        }
+    }
+    Ok(())
 }
 
-pub fn decompress_tile(data: &[u8]) -> Result<Vec<u8>, anyhow::Error> {
-    Ok(weezl::decode::Decoder::with_tiff_size_switch(weezl::BitOrder::Msb, 8).decode(data)?)
+/// Packs `nodata` into the little-endian bytes of a single sample, per
+/// `bits`/`sample_format`. Callers must have already validated `nodata` with
+/// [`validate_nodata`] using the same `bits`/`sample_format`.
+fn nodata_sample_bytes(nodata: &str, bits: u32, sample_format: SampleFormat) -> Vec<u8> {
+    let bytes_per_sample = (bits / 8).max(1) as usize;
+    match sample_format {
+        SampleFormat::Int => {
+            let value: i64 = nodata.parse().expect("validated by validate_nodata");
+            match bytes_per_sample {
+                1 => vec![value as i8 as u8],
+                2 => (value as i16).to_le_bytes().to_vec(),
+                4 => (value as i32).to_le_bytes().to_vec(),
+                _ => value.to_le_bytes().to_vec(),
+            }
+        }
+        SampleFormat::Uint => {
+            let value: u64 = nodata.parse().expect("validated by validate_nodata");
+            match bytes_per_sample {
+                1 => vec![value as u8],
+                2 => (value as u16).to_le_bytes().to_vec(),
+                4 => (value as u32).to_le_bytes().to_vec(),
+                _ => value.to_le_bytes().to_vec(),
+            }
+        }
+        SampleFormat::Float => {
+            let value: f64 = nodata.parse().expect("validated by validate_nodata");
+            match bytes_per_sample {
+                4 => (value as f32).to_le_bytes().to_vec(),
+                _ => value.to_le_bytes().to_vec(),
+            }
+        }
+    }
 }
 
 pub struct CogBuilder {
@@ -31,6 +109,13 @@ pub struct CogBuilder {
     heights: Vec<u32>,
     tile_counts: Vec<u32>,
     file_size: u64,
+    compression: Compression,
+    predictor: Predictor,
+    georeference: Option<GeoReference>,
+    bpp: Vec<u8>,
+    sample_format: SampleFormat,
+    nodata: String,
+    trailer_len: u64,
 }
 
 impl CogBuilder {
@@ -53,14 +138,20 @@ impl CogBuilder {
         Self::get_file(&self.files, &self.path)
     }
 
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         path: PathBuf,
         width: u32,
         height: u32,
         bpp: Vec<u8>,
-        signed: bool,
-        _nodata: &str,
+        sample_format: SampleFormat,
+        nodata: &str,
+        compression: Compression,
+        predictor: Predictor,
+        georeference: Option<GeoReference>,
     ) -> Result<Self, anyhow::Error> {
+        validate_nodata(nodata, &bpp, sample_format)?;
+
         let files = ThreadLocal::new();
         let mut  file = Self::get_file(&files, &path)?;
         let original_file_size = file.seek(SeekFrom::End(0))?;
@@ -87,10 +178,39 @@ impl CogBuilder {
 
         file.seek(SeekFrom::Start(0))?;
 
+        // Data too large to inline into an IFD entry (a spilled nodata string,
+        // the georeferencing arrays) is appended right after the fixed
+        // 1024-byte-per-level IFD area, in this trailer.
+        let trailer_base_offset = tile_counts.len() as u64 * 1024;
+        let mut trailer = Vec::new();
+
+        // GDAL_NODATA is an ASCII tag; if it fits inline in an 8-byte value
+        // slot (including the NUL terminator) it's written there directly,
+        // otherwise it spills into the trailer like the georeferencing data.
+        let inline_nodata = !nodata.is_empty() && nodata.len() < 8;
+        let nodata_offset = if !nodata.is_empty() && !inline_nodata {
+            let offset = trailer_base_offset + trailer.len() as u64;
+            trailer.extend_from_slice(nodata.as_bytes());
+            trailer.push(0);
+            Some(offset)
+        } else {
+            None
+        };
+
+        // The georeferencing arrays (if any) are appended after the nodata
+        // string, and only level 0's IFD points into them.
+        let geo_blob = georeference
+            .as_ref()
+            .map(|geo| geotiff::build_blob(geo, trailer_base_offset + trailer.len() as u64));
+        if let Some(blob) = &geo_blob {
+            trailer.extend_from_slice(&blob.bytes);
+        }
+        let trailer_len = trailer.len() as u64;
+
         let mut single_offset_size = (1u64, 0u64);
         let total_tiles = tile_counts.iter().map(|&c| c as u64).sum::<u64>();
-        let new_file_size =
-            (1024 * tile_counts.len() as u64 + 16 * total_tiles).max(original_file_size);
+        let new_file_size = (1024 * tile_counts.len() as u64 + trailer_len + 16 * total_tiles)
+            .max(original_file_size);
         if original_file_size >= 1024 * tile_counts.len() as u64 {
             let mut ifd_buffers = vec![0; 1024 * tile_counts.len()];
             file.read_exact(&mut ifd_buffers)?;
@@ -124,13 +244,19 @@ impl CogBuilder {
         }
 
         let mut data = Vec::new();
-        let mut indexes_offset = tile_counts.len() as u64 * 1024;
+        let mut indexes_offset = trailer_base_offset + trailer_len;
         data.extend_from_slice(&[73, 73, 43, 0, 8, 0, 0, 0, 16, 0, 0, 0, 0, 0, 0, 0]);
         for level in 0..tile_counts.len() {
             let mut ifd = Vec::new();
 
             // Number of tags
-            ifd.extend_from_slice((NUM_TAGS as u64).to_le_bytes().as_slice());
+            let num_tags = if !nodata.is_empty() { NUM_TAGS + 1 } else { NUM_TAGS };
+            let num_tags = if level == 0 && geo_blob.is_some() {
+                num_tags + 5
+            } else {
+                num_tags
+            };
+            ifd.extend_from_slice((num_tags as u64).to_le_bytes().as_slice());
 
             // TIFF new SubfileType
             ifd.extend_from_slice(&[0xFE, 0, 4, 0, 1, 0, 0, 0, 0, 0, 0, 0]);
@@ -156,7 +282,7 @@ impl CogBuilder {
 
             // TIFF compression
             ifd.extend_from_slice(&[3, 1, 4, 0, 1, 0, 0, 0, 0, 0, 0, 0]);
-            ifd.extend_from_slice(5u64.to_le_bytes().as_slice());
+            ifd.extend_from_slice(compression.tiff_tag_value().to_le_bytes().as_slice());
 
             // TIFF photometric interpretation
             ifd.extend_from_slice(&[6, 1, 4, 0, 1, 0, 0, 0, 0, 0, 0, 0]);
@@ -170,6 +296,10 @@ impl CogBuilder {
             ifd.extend_from_slice(&[0x15, 1, 4, 0, 1, 0, 0, 0, 0, 0, 0, 0]);
             ifd.extend_from_slice((bpp.len() as u64).to_le_bytes().as_slice());
 
+            // TIFF predictor
+            ifd.extend_from_slice(&[0x3D, 1, 3, 0, 1, 0, 0, 0, 0, 0, 0, 0]);
+            ifd.extend_from_slice(predictor.tiff_tag_value().to_le_bytes().as_slice());
+
             // TIFF tile width
             ifd.extend_from_slice(&[0x42, 1, 4, 0, 1, 0, 0, 0, 0, 0, 0, 0]);
             ifd.extend_from_slice((TILE_SIZE as u64).to_le_bytes().as_slice());
@@ -202,17 +332,53 @@ impl CogBuilder {
 
             // TIFF sample format
             ifd.extend_from_slice(&[0x53, 1, 3, 0, 1, 0, 0, 0, 0, 0, 0, 0]);
-            if signed {
-                ifd.extend_from_slice(2u64.to_le_bytes().as_slice());
-            } else {
-                ifd.extend_from_slice(1u64.to_le_bytes().as_slice());
+            ifd.extend_from_slice(sample_format.tiff_tag_value().to_le_bytes().as_slice());
+
+            // GeoTIFF georeferencing tags (level 0 only). These tags (0x830E,
+            // 0x8482, 0x87AF, 0x87B0, 0x87B1) are numbered below GDAL_NODATA
+            // (0xA481), so they must be emitted first to keep the IFD's tags
+            // in the ascending order TIFF6 requires.
+            if level == 0 {
+                if let Some(blob) = &geo_blob {
+                    // ModelPixelScaleTag
+                    ifd.extend_from_slice(&[0x0E, 0x83, 12, 0]);
+                    ifd.extend_from_slice(3u64.to_le_bytes().as_slice());
+                    ifd.extend_from_slice(blob.pixel_scale_offset.to_le_bytes().as_slice());
+
+                    // ModelTiepointTag
+                    ifd.extend_from_slice(&[0x82, 0x84, 12, 0]);
+                    ifd.extend_from_slice(6u64.to_le_bytes().as_slice());
+                    ifd.extend_from_slice(blob.tiepoint_offset.to_le_bytes().as_slice());
+
+                    // GeoKeyDirectoryTag
+                    ifd.extend_from_slice(&[0xAF, 0x87, 3, 0]);
+                    ifd.extend_from_slice(blob.geo_key_directory_len.to_le_bytes().as_slice());
+                    ifd.extend_from_slice(blob.geo_key_directory_offset.to_le_bytes().as_slice());
+
+                    // GeoDoubleParamsTag
+                    ifd.extend_from_slice(&[0xB0, 0x87, 12, 0]);
+                    ifd.extend_from_slice(1u64.to_le_bytes().as_slice());
+                    ifd.extend_from_slice(blob.geo_double_params_offset.to_le_bytes().as_slice());
+
+                    // GeoAsciiParamsTag
+                    ifd.extend_from_slice(&[0xB1, 0x87, 2, 0]);
+                    ifd.extend_from_slice(blob.geo_ascii_params_len.to_le_bytes().as_slice());
+                    ifd.extend_from_slice(blob.geo_ascii_params_offset.to_le_bytes().as_slice());
+                }
             }
 
-            // // GDAL nodata
-            // assert!(nodata.len() < 8);
-            // ifd.extend_from_slice(&[0x81, 0xA4, 2, 0, nodata.len() as u8 + 1, 0, 0, 0, 0, 0, 0, 0]);
-            // ifd.extend_from_slice(nodata.as_bytes());
-            // ifd.extend_from_slice(&[0; 8][..8 - nodata.len()]);
+            // GDAL_NODATA: an ASCII tag (non-standard, but the convention
+            // GDAL and other readers use to recover the nodata value).
+            if !nodata.is_empty() {
+                ifd.extend_from_slice(&[0x81, 0xA4, 2, 0]);
+                ifd.extend_from_slice((nodata.len() as u64 + 1).to_le_bytes().as_slice());
+                if inline_nodata {
+                    ifd.extend_from_slice(nodata.as_bytes());
+                    ifd.extend_from_slice(&[0; 8][..8 - nodata.len()]);
+                } else {
+                    ifd.extend_from_slice(nodata_offset.unwrap().to_le_bytes().as_slice());
+                }
+            }
 
             // Next IFD
             if level < tile_counts.len() - 1 {
@@ -228,6 +394,8 @@ impl CogBuilder {
             indexes_offset += tile_counts[level] as u64 * 16;
         }
 
+        data.extend_from_slice(&trailer);
+
         file.write_all(&data)?;
 
         if original_file_size < new_file_size {
@@ -252,6 +420,13 @@ impl CogBuilder {
             heights,
             tile_counts,
             file_size: new_file_size,
+            compression,
+            predictor,
+            georeference,
+            bpp,
+            sample_format,
+            nodata: nodata.to_string(),
+            trailer_len,
         })
     }
 
@@ -270,10 +445,27 @@ impl CogBuilder {
     pub fn levels(&self) -> u32 {
         self.tile_counts.len() as u32
     }
+    pub fn compression(&self) -> Compression {
+        self.compression
+    }
+    pub fn predictor(&self) -> Predictor {
+        self.predictor
+    }
+    pub fn georeference(&self) -> Option<&GeoReference> {
+        self.georeference.as_ref()
+    }
+    pub fn sample_format(&self) -> SampleFormat {
+        self.sample_format
+    }
+    /// The configured GDAL_NODATA value, or the empty string if none was set.
+    pub fn nodata(&self) -> &str {
+        &self.nodata
+    }
 
     fn offset_size_locations(&self, level: u32, tile_index: u32) -> (u64, u64) {
         if self.tile_counts[level as usize] > 1 {
             let offset_location = self.tile_counts.len() as u64 * 1024
+                + self.trailer_len
                 + self.tile_counts[0..(level as usize)]
                     .iter()
                     .map(|&c| c as u64)
@@ -330,13 +522,59 @@ impl CogBuilder {
         Ok(())
     }
 
+    /// Marks a tile as containing no data. If a nodata value was configured,
+    /// this actually writes out a real (compressed) tile filled with that
+    /// value, so the tile reports the intended fill rather than relying on
+    /// readers to notice a zero byte count and substitute their own nodata
+    /// fill. With no nodata value configured, this falls back to setting the
+    /// tile's offset to zero and writing no tile data.
     pub fn write_nodata_tile(&mut self, level: u32, index: u32) -> Result<(), anyhow::Error> {
-        let offset_location = self.offset_size_locations(level, index).0;
+        if self.nodata.is_empty() {
+            let offset_location = self.offset_size_locations(level, index).0;
+            let mut file = self.file()?;
+            file.seek(SeekFrom::Start(offset_location))?;
+            file.write_all(&0u64.to_le_bytes())?;
+            return Ok(file.flush()?);
+        }
 
-        let mut file = self.file()?;
-        file.seek(SeekFrom::Start(offset_location))?;
-        file.write_all(&0u64.to_le_bytes())?;
-        Ok(file.flush()?)
+        let tile = self.nodata_fill_tile(level, index);
+        self.write_tile(level, index, &tile)
+    }
+
+    /// The byte width of a single sample, taken from the first entry of
+    /// `bpp` (samples are assumed to share a common bit depth, as elsewhere
+    /// in this module).
+    fn bytes_per_sample(&self) -> usize {
+        (self.bpp[0] as usize / 8).max(1)
+    }
+
+    fn nodata_fill_tile(&self, level: u32, index: u32) -> Vec<u8> {
+        let samples_per_pixel = self.bpp.len();
+        let bits = self.bpp[0] as u32;
+        let sample = nodata_sample_bytes(&self.nodata, bits, self.sample_format);
+
+        // Edge tiles cover only the image's remaining width/height, matching
+        // the tile size `read_region` decodes them with.
+        let tiles_across = self.tiles_across(level);
+        let tile_width = (self.width(level) - (index % tiles_across) * TILE_SIZE).min(TILE_SIZE);
+        let tile_height = (self.height(level) - (index / tiles_across) * TILE_SIZE).min(TILE_SIZE);
+
+        let mut raw =
+            Vec::with_capacity(sample.len() * samples_per_pixel * (tile_width * tile_height) as usize);
+        for _ in 0..tile_width * tile_height {
+            for _ in 0..samples_per_pixel {
+                raw.extend_from_slice(&sample);
+            }
+        }
+
+        compress_tile(
+            &raw,
+            self.compression,
+            self.predictor,
+            samples_per_pixel,
+            tile_width,
+            sample.len(),
+        )
     }
 
     pub fn read_tile(&self, level: u32, index: u32) -> Result<Option<Vec<u8>>, anyhow::Error> {
@@ -368,6 +606,100 @@ impl CogBuilder {
 
         Ok(Some(tile))
     }
+
+    /// Decodes an entire overview level into a row-major buffer of
+    /// `width(level) * height(level) * samples_per_pixel * bytes_per_sample`
+    /// bytes.
+    pub fn read_level(&self, level: u32) -> Result<Vec<u8>, anyhow::Error> {
+        self.read_region(level, 0, 0, self.width(level), self.height(level))
+    }
+
+    /// Decodes the `w`x`h` window starting at `(x, y)` of overview `level`
+    /// into a row-major buffer, stitching together however many tiles the
+    /// window spans. Tiles with no data written (a zero byte count) are
+    /// filled with the configured nodata value, or zeroes if none was set.
+    pub fn read_region(
+        &self,
+        level: u32,
+        x: u32,
+        y: u32,
+        w: u32,
+        h: u32,
+    ) -> Result<Vec<u8>, anyhow::Error> {
+        anyhow::ensure!(w > 0 && h > 0, "region must be non-empty");
+        let level_width = self.width(level);
+        let level_height = self.height(level);
+        anyhow::ensure!(
+            x.checked_add(w).is_some_and(|edge| edge <= level_width)
+                && y.checked_add(h).is_some_and(|edge| edge <= level_height),
+            "region ({x}, {y}, {w}, {h}) is out of bounds for level {level} ({level_width}x{level_height})"
+        );
+
+        let samples_per_pixel = self.bpp.len();
+        let bytes_per_sample = self.bytes_per_sample();
+        let pixel_bytes = samples_per_pixel * bytes_per_sample;
+        let tiles_across = self.tiles_across(level);
+
+        let fill_sample = if self.nodata.is_empty() {
+            vec![0u8; bytes_per_sample]
+        } else {
+            nodata_sample_bytes(&self.nodata, self.bpp[0] as u32, self.sample_format)
+        };
+
+        let mut out = vec![0u8; w as usize * h as usize * pixel_bytes];
+        let out_row_bytes = w as usize * pixel_bytes;
+
+        let tile_x0 = x / TILE_SIZE;
+        let tile_y0 = y / TILE_SIZE;
+        let tile_x1 = (x + w - 1) / TILE_SIZE;
+        let tile_y1 = (y + h - 1) / TILE_SIZE;
+
+        for ty in tile_y0..=tile_y1 {
+            let tile_origin_y = ty * TILE_SIZE;
+            let tile_height = (level_height - tile_origin_y).min(TILE_SIZE);
+            for tx in tile_x0..=tile_x1 {
+                let tile_origin_x = tx * TILE_SIZE;
+                let tile_width = (level_width - tile_origin_x).min(TILE_SIZE);
+                let index = ty * tiles_across + tx;
+
+                let tile = match self.read_tile(level, index)? {
+                    Some(compressed) => decompress_tile(
+                        &compressed,
+                        self.compression,
+                        self.predictor,
+                        samples_per_pixel,
+                        tile_width,
+                        bytes_per_sample,
+                    )?,
+                    None => fill_sample
+                        .repeat(samples_per_pixel * (tile_width * tile_height) as usize),
+                };
+                let tile_row_bytes = tile_width as usize * pixel_bytes;
+
+                let overlap_x0 = tile_origin_x.max(x);
+                let overlap_x1 = (tile_origin_x + tile_width).min(x + w);
+                if overlap_x0 >= overlap_x1 {
+                    continue;
+                }
+                let copy_bytes = (overlap_x1 - overlap_x0) as usize * pixel_bytes;
+                let src_col = (overlap_x0 - tile_origin_x) as usize * pixel_bytes;
+                let dst_col = (overlap_x0 - x) as usize * pixel_bytes;
+
+                for row in 0..tile_height {
+                    let global_y = tile_origin_y + row;
+                    if global_y < y || global_y >= y + h {
+                        continue;
+                    }
+                    let src_start = row as usize * tile_row_bytes + src_col;
+                    let dst_start = (global_y - y) as usize * out_row_bytes + dst_col;
+                    out[dst_start..dst_start + copy_bytes]
+                        .copy_from_slice(&tile[src_start..src_start + copy_bytes]);
+                }
+            }
+        }
+
+        Ok(out)
+    }
 }
 
 #[cfg(test)]
@@ -376,9 +708,38 @@ mod tests {
 
     #[test]
     fn it_works() {
-        let mut builder = CogBuilder::new("test.tiff".into(), 4096, 4096, vec![8], false, "0").unwrap();
-        let compressed = compress_tile(&vec![255u8; 1024 * 1024]);
-        let compressed2 = compress_tile(&vec![127u8; 1024 * 1024]);
+        let mut builder = CogBuilder::new(
+            "test.tiff".into(),
+            4096,
+            4096,
+            vec![8],
+            SampleFormat::Uint,
+            "0",
+            Compression::Lzw,
+            Predictor::Horizontal,
+            Some(GeoReference {
+                epsg: 32633,
+                pixel_scale: [30.0, 30.0, 0.0],
+                tiepoint: [0.0, 0.0, 0.0, 500000.0, 4649776.0, 0.0],
+            }),
+        )
+        .unwrap();
+        let compressed = compress_tile(
+            &vec![255u8; 1024 * 1024],
+            Compression::Lzw,
+            Predictor::Horizontal,
+            1,
+            TILE_SIZE,
+            1,
+        );
+        let compressed2 = compress_tile(
+            &vec![127u8; 1024 * 1024],
+            Compression::Lzw,
+            Predictor::Horizontal,
+            1,
+            TILE_SIZE,
+            1,
+        );
 
         for level in 0..3 {
             for i in 0..(4u32 >> level).pow(2) {
@@ -389,5 +750,48 @@ mod tests {
                 }
             }
         }
+
+        assert_eq!(builder.read_level(2).unwrap(), vec![255u8; 1024 * 1024]);
+
+        // Straddles the boundary between level 1's (0, 0) tile (255) and its
+        // (1, 0) neighbor (127), exercising the tile-stitching path.
+        let region = builder.read_region(1, 1020, 0, 8, 1).unwrap();
+        assert_eq!(region, [vec![255u8; 4], vec![127u8; 4]].concat());
+
+        assert_eq!(builder.nodata(), "0");
+        assert_eq!(builder.sample_format(), SampleFormat::Uint);
+        builder.write_nodata_tile(0, 0).unwrap();
+        assert!(builder.read_tile(0, 0).unwrap().is_some());
+        assert_eq!(
+            builder.read_region(0, 0, 0, 4, 4).unwrap(),
+            vec![0u8; 4 * 4]
+        );
+    }
+
+    #[test]
+    fn float_samples() {
+        let mut builder = CogBuilder::new(
+            "test_float.tiff".into(),
+            2048,
+            2048,
+            vec![32],
+            SampleFormat::Float,
+            "-9999",
+            Compression::Deflate,
+            Predictor::FloatingPoint,
+            None,
+        )
+        .unwrap();
+
+        assert_eq!(builder.sample_format(), SampleFormat::Float);
+        builder.write_nodata_tile(0, 0).unwrap();
+        assert!(builder.read_tile(0, 0).unwrap().is_some());
+
+        let region = builder.read_region(0, 0, 0, 2, 2).unwrap();
+        let samples: Vec<f32> = region
+            .chunks_exact(4)
+            .map(|b| f32::from_le_bytes(b.try_into().unwrap()))
+            .collect();
+        assert_eq!(samples, vec![-9999.0f32; 4]);
     }
 }
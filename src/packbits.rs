@@ -0,0 +1,73 @@
+//! The standard TIFF PackBits byte run-length scheme (TIFF 6.0 spec, section 9).
+
+/// Encodes `data` using PackBits: literal runs are prefixed by `n - 1` for a
+/// run of `n` (1..=128) distinct bytes, and repeat runs are prefixed by
+/// `257 - n` for `n` (2..=128) repetitions of a single byte.
+pub fn encode(data: &[u8]) -> Vec<u8> {
+    let mut out = Vec::new();
+    let mut i = 0;
+    while i < data.len() {
+        let mut run_len = 1;
+        while run_len < 128 && i + run_len < data.len() && data[i + run_len] == data[i] {
+            run_len += 1;
+        }
+        if run_len >= 2 {
+            out.push((257 - run_len) as u8);
+            out.push(data[i]);
+            i += run_len;
+            continue;
+        }
+
+        let start = i;
+        let mut len = 1;
+        i += 1;
+        while len < 128 && i < data.len() {
+            if i + 1 < data.len() && data[i] == data[i + 1] {
+                break;
+            }
+            len += 1;
+            i += 1;
+        }
+        out.push((len - 1) as u8);
+        out.extend_from_slice(&data[start..start + len]);
+    }
+    out
+}
+
+/// Decodes a PackBits byte stream produced by [`encode`] (or any conforming encoder).
+pub fn decode(data: &[u8]) -> Result<Vec<u8>, anyhow::Error> {
+    let mut out = Vec::new();
+    let mut i = 0;
+    while i < data.len() {
+        let n = data[i] as i8;
+        i += 1;
+        if n >= 0 {
+            let len = n as usize + 1;
+            anyhow::ensure!(i + len <= data.len(), "truncated PackBits literal run");
+            out.extend_from_slice(&data[i..i + len]);
+            i += len;
+        } else if n != -128 {
+            anyhow::ensure!(i < data.len(), "truncated PackBits repeat run");
+            let len = (1 - n as i32) as usize;
+            out.extend(std::iter::repeat_n(data[i], len));
+            i += 1;
+        }
+        // n == -128 is a documented no-op, skip it.
+    }
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn roundtrip() {
+        let mut data = vec![1u8, 1, 1, 1, 2, 3, 4, 4, 4];
+        data.extend(vec![9u8; 200]);
+        data.extend(0..=255u8);
+
+        let encoded = encode(&data);
+        assert_eq!(decode(&encoded).unwrap(), data);
+    }
+}
@@ -0,0 +1,205 @@
+//! TIFF differencing predictors (tag 0x013D), applied to a tile's raw samples
+//! before compression and reversed after decompression.
+
+/// Which TIFF `Predictor` a tile's samples were transformed with prior to
+/// compression.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Predictor {
+    /// No prediction (TIFF tag value 1).
+    None,
+    /// Horizontal differencing (TIFF tag value 2): each sample is replaced by
+    /// its difference from the same channel's sample one pixel to the left.
+    /// Differences are taken between single bytes, `samples_per_pixel` bytes
+    /// apart, so this is per-sample differencing only for 8-bit samples;
+    /// wider samples are not differenced as whole values.
+    Horizontal,
+    /// The floating point predictor (TIFF tag value 3): each row's samples
+    /// are first split into byte planes (all most-significant bytes, then
+    /// all next-most-significant bytes, and so on), then horizontally
+    /// differenced byte by byte. Meant
+    /// to pair with [`crate::SampleFormat::Float`] samples, where plain
+    /// [`Predictor::Horizontal`] differencing does little for the high-order
+    /// bytes of an IEEE float.
+    FloatingPoint,
+}
+
+impl Predictor {
+    pub(crate) fn tiff_tag_value(self) -> u64 {
+        match self {
+            Predictor::None => 1,
+            Predictor::Horizontal => 2,
+            Predictor::FloatingPoint => 3,
+        }
+    }
+}
+
+/// Applies `predictor` to a tile's decoded samples, row by row, using `width`
+/// (the tile's actual sample width, which may be less than `TILE_SIZE` for a
+/// right/bottom edge tile), `samples_per_pixel`, and `bytes_per_sample` (the
+/// size of one sample, e.g. 4 for a 32-bit float) to find the byte strides
+/// involved. `bytes_per_sample` is only used by
+/// [`Predictor::FloatingPoint`]; [`Predictor::Horizontal`] works on bytes.
+pub fn apply(
+    data: &[u8],
+    predictor: Predictor,
+    samples_per_pixel: usize,
+    width: u32,
+    bytes_per_sample: usize,
+) -> Vec<u8> {
+    match predictor {
+        Predictor::None => data.to_vec(),
+        Predictor::Horizontal => horizontal_difference(data, samples_per_pixel, width as usize),
+        Predictor::FloatingPoint => {
+            floating_point_difference(data, samples_per_pixel, width as usize, bytes_per_sample)
+        }
+    }
+}
+
+/// Reverses [`apply`].
+pub fn unapply(
+    data: &[u8],
+    predictor: Predictor,
+    samples_per_pixel: usize,
+    width: u32,
+    bytes_per_sample: usize,
+) -> Vec<u8> {
+    match predictor {
+        Predictor::None => data.to_vec(),
+        Predictor::Horizontal => horizontal_undifference(data, samples_per_pixel, width as usize),
+        Predictor::FloatingPoint => {
+            floating_point_undifference(data, samples_per_pixel, width as usize, bytes_per_sample)
+        }
+    }
+}
+
+fn horizontal_difference(data: &[u8], samples_per_pixel: usize, width: usize) -> Vec<u8> {
+    let mut out = data.to_vec();
+    let row_bytes = width * samples_per_pixel;
+    for row in out.chunks_mut(row_bytes) {
+        for i in (samples_per_pixel..row.len()).rev() {
+            row[i] = row[i].wrapping_sub(row[i - samples_per_pixel]);
+        }
+    }
+    out
+}
+
+fn horizontal_undifference(data: &[u8], samples_per_pixel: usize, width: usize) -> Vec<u8> {
+    let mut out = data.to_vec();
+    let row_bytes = width * samples_per_pixel;
+    for row in out.chunks_mut(row_bytes) {
+        for i in samples_per_pixel..row.len() {
+            row[i] = row[i].wrapping_add(row[i - samples_per_pixel]);
+        }
+    }
+    out
+}
+
+/// Rearranges each row's samples into byte planes (plane 0 holds every
+/// sample's most-significant byte, plane 1 the next byte, and so on down to
+/// the least-significant byte), then horizontally differences the
+/// rearranged row a byte at a time.
+fn floating_point_difference(
+    data: &[u8],
+    samples_per_pixel: usize,
+    width: usize,
+    bytes_per_sample: usize,
+) -> Vec<u8> {
+    let mut out = data.to_vec();
+    let samples_per_row = width * samples_per_pixel;
+    let row_bytes = samples_per_row * bytes_per_sample;
+    for row in out.chunks_mut(row_bytes) {
+        let original = row.to_vec();
+        for (i, &byte) in original.iter().enumerate() {
+            let sample = i / bytes_per_sample;
+            let plane = bytes_per_sample - 1 - (i % bytes_per_sample);
+            row[plane * samples_per_row + sample] = byte;
+        }
+        for i in (1..row.len()).rev() {
+            row[i] = row[i].wrapping_sub(row[i - 1]);
+        }
+    }
+    out
+}
+
+/// Reverses [`floating_point_difference`].
+fn floating_point_undifference(
+    data: &[u8],
+    samples_per_pixel: usize,
+    width: usize,
+    bytes_per_sample: usize,
+) -> Vec<u8> {
+    let mut out = data.to_vec();
+    let samples_per_row = width * samples_per_pixel;
+    let row_bytes = samples_per_row * bytes_per_sample;
+    for row in out.chunks_mut(row_bytes) {
+        for i in 1..row.len() {
+            row[i] = row[i].wrapping_add(row[i - 1]);
+        }
+        let planar = row.to_vec();
+        for (i, &byte) in planar.iter().enumerate() {
+            let plane = i / samples_per_row;
+            let sample = i % samples_per_row;
+            row[sample * bytes_per_sample + (bytes_per_sample - 1 - plane)] = byte;
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn roundtrip() {
+        let width = 17usize;
+        let samples_per_pixel = 3usize;
+        let data: Vec<u8> = (0..width * samples_per_pixel * 5)
+            .map(|i| (i * 37) as u8)
+            .collect();
+
+        let differenced = apply(&data, Predictor::Horizontal, samples_per_pixel, width as u32, 1);
+        assert_eq!(
+            unapply(&differenced, Predictor::Horizontal, samples_per_pixel, width as u32, 1),
+            data
+        );
+    }
+
+    #[test]
+    fn floating_point_byte_layout() {
+        // Two f32 samples, one pixel each: 1.0 = [00,00,80,3F], 2.0 = [00,00,00,40]
+        // in little-endian memory order. The planar layout (before horizontal
+        // differencing) should start with the most-significant bytes: 3F, 40.
+        let data: Vec<u8> = [1.0f32, 2.0f32].iter().flat_map(|v| v.to_le_bytes()).collect();
+        let differenced = floating_point_difference(&data, 1, 2, 4);
+        assert_eq!(differenced[0], 0x3F);
+        assert_eq!(differenced[1], 0x40u8.wrapping_sub(0x3F));
+    }
+
+    #[test]
+    fn floating_point_roundtrip() {
+        let width = 13usize;
+        let samples_per_pixel = 2usize;
+        let values: Vec<f32> = (0..width * samples_per_pixel * 4)
+            .map(|i| (i as f32) * 0.5 - 10.0)
+            .collect();
+        let data: Vec<u8> = values.iter().flat_map(|v| v.to_le_bytes()).collect();
+
+        let differenced = apply(
+            &data,
+            Predictor::FloatingPoint,
+            samples_per_pixel,
+            width as u32,
+            4,
+        );
+        assert_eq!(
+            unapply(
+                &differenced,
+                Predictor::FloatingPoint,
+                samples_per_pixel,
+                width as u32,
+                4,
+            ),
+            data
+        );
+    }
+}
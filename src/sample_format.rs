@@ -0,0 +1,22 @@
+//! Which numeric representation a tile's samples are stored in (TIFF
+//! `SampleFormat` tag, 0x0153).
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum SampleFormat {
+    /// Unsigned integer samples (TIFF tag value 1).
+    Uint,
+    /// Two's-complement signed integer samples (TIFF tag value 2).
+    Int,
+    /// IEEE floating point samples (TIFF tag value 3).
+    Float,
+}
+
+impl SampleFormat {
+    pub(crate) fn tiff_tag_value(self) -> u64 {
+        match self {
+            SampleFormat::Uint => 1,
+            SampleFormat::Int => 2,
+            SampleFormat::Float => 3,
+        }
+    }
+}